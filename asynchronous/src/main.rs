@@ -1,60 +1,133 @@
-use futures::future;
-use std::{error::Error, process, time::Duration};
+use http::{Method, Request, Router};
+use static_files::{StaticFileError, StaticFiles};
+use std::{
+    error::Error,
+    process,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
 use tokio::{
     fs,
     io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
     net::{TcpListener, TcpStream},
-    task::JoinHandle,
+    task::JoinSet,
     time,
 };
 
+mod http;
+mod static_files;
+
 static ADDRESS: &str = "127.0.0.1";
 static DEFAULT_PORT: &str = "7878";
-static NUM_TASKS: usize = 10;
+static WEB_ROOT: &str = "../pages";
+static FALLBACK_PAGE: &str = "<html><body><h1>Page unavailable</h1></body></html>";
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
     let listener = bind_listener(ADDRESS, DEFAULT_PORT).await;
-    let mut handles: Vec<JoinHandle<()>> = Vec::with_capacity(NUM_TASKS);
-
-    for i in 0..NUM_TASKS {
-        let (stream, address) = listener.accept().await.unwrap_or_else(|err| {
-            eprintln!("[ERROR] Failed to fetch next item in stream: {err}");
-            process::exit(1);
-        });
+    let connections_accepted = Arc::new(AtomicU64::new(0));
+    let router = Arc::new(build_router(Arc::clone(&connections_accepted)));
+    let static_files = Arc::new(StaticFiles::new(WEB_ROOT).await.unwrap_or_else(|err| {
+        eprintln!("[ERROR] Failed to open web root {WEB_ROOT}: {err}");
+        process::exit(1);
+    }));
+    let mut tasks = JoinSet::new();
+
+    loop {
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => {
+                if let Err(err) = result {
+                    eprintln!("[ERROR] Failed to listen for Ctrl-C: {err}");
+                }
+                println!("[INFO] Received Ctrl-C; shutting down");
+                break;
+            }
+            accepted = listener.accept() => {
+                let (stream, address) = accepted.unwrap_or_else(|err| {
+                    eprintln!("[ERROR] Failed to fetch next item in stream: {err}");
+                    process::exit(1);
+                });
+                let router = Arc::clone(&router);
+                let static_files = Arc::clone(&static_files);
+                let request_count = connections_accepted.fetch_add(1, Ordering::SeqCst) + 1;
 
-        let handle = tokio::spawn(async move {
-            println!("[EVENT] Received request {i} on socket address {address}");
+                tasks.spawn(async move {
+                    println!("[EVENT] Received request {request_count} on socket address {address}");
 
-            handle_connection(stream).await;
-        });
+                    handle_connection(stream, &router, &static_files).await;
+                });
 
-        handles.push(handle);
+                // Reap finished tasks as we go instead of letting the set grow forever;
+                // join_all would only happen at shutdown, which leaks a handle per
+                // connection for the lifetime of a long-running server.
+                while tasks.try_join_next().is_some() {}
+            }
+        }
     }
 
-    future::join_all(handles).await;
+    tasks.join_all().await;
 
     Ok(())
 }
 
-async fn handle_connection(mut stream: TcpStream) {
-    let buf_reader = BufReader::new(&mut stream);
-    let buffer = buf_reader.lines().next_line().await.unwrap_or_else(|err| {
-        eprintln!("[ERROR] Failed to read request line: {err}");
-        process::exit(1);
-    });
+fn build_router(connections_accepted: Arc<AtomicU64>) -> Router {
+    Router::new()
+        .route(
+            Method::Get,
+            "/sleep",
+            Box::new(|_| {
+                Box::pin(async {
+                    time::sleep(Duration::from_secs(10)).await;
+                    (
+                        "HTTP/1.1 200 OK",
+                        "text/html; charset=utf-8",
+                        parse_file("../pages/sleep.html").await,
+                    )
+                })
+            }),
+        )
+        .route(
+            Method::Get,
+            "/metrics",
+            Box::new(move |_| {
+                let connections_accepted = Arc::clone(&connections_accepted);
+
+                Box::pin(async move {
+                    // The async server has no `ThreadPool`, so there's no `queue_depth`
+                    // or `completed_jobs` to report here; `connections_accepted` is the
+                    // only counter this server tracks.
+                    let body =
+                        format!("connections_accepted {}\n", connections_accepted.load(Ordering::SeqCst));
+
+                    ("HTTP/1.1 200 OK", "text/plain; charset=utf-8", body)
+                })
+            }),
+        )
+}
 
-    let request_line = match buffer {
-        Some(line) => line,
-        None => {
-            eprintln!("[ERROR] No lines found in buffer");
-            return;
+async fn handle_connection(mut stream: TcpStream, router: &Router, static_files: &StaticFiles) {
+    let request = {
+        let mut lines = BufReader::new(&mut stream).lines();
+
+        match Request::parse(&mut lines).await {
+            Ok(Some(request)) => request,
+            Ok(None) => {
+                eprintln!("[ERROR] No lines found in buffer");
+                return;
+            }
+            Err(err) => {
+                eprintln!("[ERROR] Failed to read request line: {err}");
+                return;
+            }
         }
     };
 
-    let response = generate_response(&request_line).await;
+    let response = generate_response(&request, router, static_files).await;
 
-    if let Err(err) = stream.write_all(response.as_bytes()).await {
+    if let Err(err) = stream.write_all(&response).await {
         eprintln!("[ERROR] Failed to write response: {err}");
     };
 }
@@ -86,21 +159,38 @@ async fn bind_listener(address: &str, port: &str) -> TcpListener {
     }
 }
 
-async fn generate_response(request_line: &str) -> String {
-    let (status_line, file_path) = match &request_line[..] {
-        "GET / HTTP/1.1" => ("HTTP/1.1 200 OK", "../pages/index.html"),
-        "GET /sleep HTTP/1.1" => {
-            time::sleep(Duration::from_secs(10)).await;
-            ("HTTP/1.1 200 OK", "../pages/sleep.html")
+async fn generate_response(request: &Request, router: &Router, static_files: &StaticFiles) -> Vec<u8> {
+    if let Some((status_line, content_type, contents)) = router.dispatch(request).await {
+        return build_response(status_line, content_type, contents.as_bytes());
+    }
+
+    match static_files.serve(request.path()).await {
+        Ok((contents, content_type)) => build_response("HTTP/1.1 200 OK", content_type, &contents),
+        Err(StaticFileError::Forbidden) => {
+            build_response("HTTP/1.1 403 FORBIDDEN", "text/plain; charset=utf-8", b"403 Forbidden")
         }
-        _ => ("HTTP/1.1 404 NOT FOUND", "../pages/404.html"),
-    };
+        Err(StaticFileError::NotFound) => build_response(
+            "HTTP/1.1 404 NOT FOUND",
+            "text/html; charset=utf-8",
+            parse_file("../pages/404.html").await.as_bytes(),
+        ),
+    }
+}
 
-    let contents = fs::read_to_string(file_path).await.unwrap_or_else(|err| {
-        eprintln!("[ERROR] Failed to read file: {err}");
-        process::exit(1);
-    });
-    let length = contents.len();
+fn build_response(status_line: &str, content_type: &str, body: &[u8]) -> Vec<u8> {
+    let mut response = format!(
+        "{status_line}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\r\n",
+        body.len()
+    )
+    .into_bytes();
+    response.extend_from_slice(body);
+
+    response
+}
 
-    format!("{status_line}\r\nContent-Length: {length}\r\n\r\n{contents}")
+async fn parse_file(file_path: &str) -> String {
+    fs::read_to_string(file_path).await.unwrap_or_else(|err| {
+        eprintln!("[ERROR] Failed to read page {file_path}: {err}");
+        FALLBACK_PAGE.to_string()
+    })
 }
@@ -0,0 +1,68 @@
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Serves files out of a fixed root directory, rejecting any request that would
+/// resolve to a path outside of it.
+pub struct StaticFiles {
+    root: PathBuf,
+}
+
+/// Why a [`StaticFiles::serve`] call didn't return file contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StaticFileError {
+    /// The resolved path escaped the configured root (e.g. via `..` or an absolute path).
+    Forbidden,
+    /// No file exists at the resolved path.
+    NotFound,
+}
+
+impl StaticFiles {
+    /// Creates a `StaticFiles` rooted at `root`, which must already exist.
+    pub async fn new(root: impl Into<PathBuf>) -> std::io::Result<StaticFiles> {
+        Ok(StaticFiles {
+            root: fs::canonicalize(root.into()).await?,
+        })
+    }
+
+    /// Resolves an HTTP request target (e.g. `/images/cat.png`) to a file under the
+    /// root and returns its bytes alongside a guessed `Content-Type`.
+    pub async fn serve(&self, target: &str) -> Result<(Vec<u8>, &'static str), StaticFileError> {
+        let relative = match target.trim_start_matches('/') {
+            "" => "index.html",
+            path => path,
+        };
+
+        if Path::new(relative).is_absolute() {
+            return Err(StaticFileError::Forbidden);
+        }
+
+        let candidate = self.root.join(relative);
+        let canonical = fs::canonicalize(&candidate)
+            .await
+            .map_err(|_| StaticFileError::NotFound)?;
+
+        if !canonical.starts_with(&self.root) {
+            return Err(StaticFileError::Forbidden);
+        }
+
+        let contents = fs::read(&canonical).await.map_err(|_| StaticFileError::NotFound)?;
+
+        Ok((contents, content_type_for(&canonical)))
+    }
+}
+
+fn content_type_for(path: &Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("js") => "text/javascript; charset=utf-8",
+        Some("json") => "application/json",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("svg") => "image/svg+xml",
+        Some("ico") => "image/x-icon",
+        Some("txt") => "text/plain; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
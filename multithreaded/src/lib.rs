@@ -1,6 +1,9 @@
-pub use self::thread_pool::ThreadPool;
+pub use self::thread_pool::{PoolCreationError, ThreadPool};
 pub use self::worker::Worker;
 
+pub mod http;
+pub mod shutdown;
+pub mod static_files;
 mod thread_pool;
 mod worker;
 
@@ -1,47 +1,131 @@
-use multithreaded::ThreadPool;
+use multithreaded::{
+    ThreadPool,
+    http::{Method, Request, Router},
+    shutdown,
+    static_files::{StaticFileError, StaticFiles},
+};
 use std::{
     fs,
-    io::{BufReader, prelude::*},
+    io::{self, BufReader, prelude::*},
     net::{TcpListener, TcpStream},
-    process, thread,
+    process,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
+    thread,
     time::Duration,
 };
 
 static ADDRESS: &str = "127.0.0.1";
 static DEFAULT_PORT: &str = "7878";
+static POLL_INTERVAL: Duration = Duration::from_millis(100);
+static WEB_ROOT: &str = "../pages";
+static FALLBACK_PAGE: &str = "<html><body><h1>Page unavailable</h1></body></html>";
 
 fn main() {
     let listener = bind_listener(ADDRESS, DEFAULT_PORT);
-    let pool = ThreadPool::new(3);
+    listener.set_nonblocking(true).unwrap_or_else(|err| {
+        eprintln!("[ERROR] Failed to set listener non-blocking: {err}");
+        process::exit(1);
+    });
 
-    // Shut down after processing 10 requests to test exit logic
-    for stream in listener.incoming().take(10) {
-        let stream = stream.unwrap_or_else(|err| {
-            eprintln!("[ERROR] Failed to fetch next item in stream: {err}");
-            process::exit(1);
-        });
+    // `pool` is never cloned into an `Arc`, so main is its sole owner: when the accept
+    // loop below breaks, dropping `pool` runs `ThreadPool::drop` synchronously and joins
+    // every worker, so in-flight jobs (e.g. a pending `/sleep`) finish before we exit.
+    let pool = ThreadPool::with_available_parallelism();
+    let connections_accepted = Arc::new(AtomicU64::new(0));
+    let router = Arc::new(build_router(
+        pool.completed_jobs_handle(),
+        pool.queue_depth_handle(),
+        Arc::clone(&connections_accepted),
+    ));
+    let static_files = Arc::new(StaticFiles::new(WEB_ROOT).unwrap_or_else(|err| {
+        eprintln!("[ERROR] Failed to open web root {WEB_ROOT}: {err}");
+        process::exit(1);
+    }));
+    let shutting_down = shutdown::install_handler();
+
+    while !shutting_down.load(Ordering::SeqCst) {
+        let stream = match listener.accept() {
+            Ok((stream, _address)) => stream,
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+            Err(err) => {
+                eprintln!("[ERROR] Failed to fetch next item in stream: {err}");
+                continue;
+            }
+        };
+        connections_accepted.fetch_add(1, Ordering::SeqCst);
+        let router = Arc::clone(&router);
+        let static_files = Arc::clone(&static_files);
 
-        pool.execute(|| handle_connection(stream));
+        pool.execute(move || handle_connection(stream, &router, &static_files));
     }
+
+    println!("[INFO] Accept loop stopped; waiting for in-flight jobs to finish");
+    drop(pool);
+}
+
+fn build_router(
+    completed_jobs: Arc<AtomicU64>,
+    queue_depth: Arc<AtomicUsize>,
+    connections_accepted: Arc<AtomicU64>,
+) -> Router {
+    Router::new()
+        .route(
+            Method::Get,
+            "/sleep",
+            Box::new(|_| {
+                thread::sleep(Duration::from_secs(5));
+                ("HTTP/1.1 200 OK", "text/html; charset=utf-8", read_page("sleep.html"))
+            }),
+        )
+        .route(
+            Method::Get,
+            "/metrics",
+            Box::new(move |_| {
+                // This request is itself queued/executing while the body below is built, so
+                // exclude it from the count to report the depth of the jobs behind it.
+                let body = format!(
+                    "connections_accepted {}\njobs_completed {}\nqueue_depth {}\n",
+                    connections_accepted.load(Ordering::SeqCst),
+                    completed_jobs.load(Ordering::SeqCst),
+                    queue_depth.load(Ordering::SeqCst).saturating_sub(1),
+                );
+
+                ("HTTP/1.1 200 OK", "text/plain; charset=utf-8", body)
+            }),
+        )
+}
+
+fn read_page(filename: &str) -> String {
+    fs::read_to_string(format!("{WEB_ROOT}/{filename}")).unwrap_or_else(|err| {
+        eprintln!("[ERROR] Failed to read page {filename}: {err}");
+        FALLBACK_PAGE.to_string()
+    })
 }
 
-fn handle_connection(mut stream: TcpStream) {
+fn handle_connection(mut stream: TcpStream, router: &Router, static_files: &StaticFiles) {
     let buf_reader = BufReader::new(&stream);
 
-    let request_line = match buf_reader.lines().next().unwrap_or_else(|| {
-        eprintln!("[ERROR] No lines found in buffer");
-        process::exit(1);
-    }) {
-        Ok(line) => line,
+    let request = match Request::parse(buf_reader.lines()) {
+        Ok(Some(request)) => request,
+        Ok(None) => {
+            eprintln!("[ERROR] No lines found in buffer");
+            return;
+        }
         Err(err) => {
             eprintln!("[ERROR] Failed to read request line: {err}");
             return;
         }
     };
 
-    let response = generate_response(&request_line);
+    let response = generate_response(&request, router, static_files);
 
-    if let Err(err) = stream.write_all(response.as_bytes()) {
+    if let Err(err) = stream.write_all(&response) {
         eprintln!("[ERROR] Failed to write response: {err}");
     };
 }
@@ -74,21 +158,29 @@ fn bind_listener(address: &str, port: &str) -> TcpListener {
     }
 }
 
-fn generate_response(request_line: &str) -> String {
-    let (status_line, file_path) = match &request_line[..] {
-        "GET / HTTP/1.1" => ("HTTP/1.1 200 OK", "../pages/index.html"),
-        "GET /sleep HTTP/1.1" => {
-            thread::sleep(Duration::from_secs(5));
-            ("HTTP/1.1 200 OK", "../pages/sleep.html")
+fn generate_response(request: &Request, router: &Router, static_files: &StaticFiles) -> Vec<u8> {
+    if let Some((status_line, content_type, contents)) = router.dispatch(request) {
+        return build_response(status_line, content_type, contents.as_bytes());
+    }
+
+    match static_files.serve(request.path()) {
+        Ok((contents, content_type)) => build_response("HTTP/1.1 200 OK", content_type, &contents),
+        Err(StaticFileError::Forbidden) => {
+            build_response("HTTP/1.1 403 FORBIDDEN", "text/plain; charset=utf-8", b"403 Forbidden")
         }
-        _ => ("HTTP/1.1 404 NOT FOUND", "../pages/404.html"),
-    };
+        Err(StaticFileError::NotFound) => {
+            build_response("HTTP/1.1 404 NOT FOUND", "text/html; charset=utf-8", read_page("404.html").as_bytes())
+        }
+    }
+}
 
-    let contents = fs::read_to_string(file_path).unwrap_or_else(|err| {
-        eprintln!("[ERROR] Failed to read file: {err}");
-        process::exit(1);
-    });
-    let length = contents.len();
+fn build_response(status_line: &str, content_type: &str, body: &[u8]) -> Vec<u8> {
+    let mut response = format!(
+        "{status_line}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\r\n",
+        body.len()
+    )
+    .into_bytes();
+    response.extend_from_slice(body);
 
-    format!("{status_line}\r\nContent-Length: {length}\r\n\r\n{contents}")
+    response
 }
@@ -1,10 +1,36 @@
 use super::{Job, worker::Worker};
-use std::sync::{Arc, Mutex, mpsc};
+use std::{
+    fmt,
+    num::NonZeroUsize,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+/// The default pool size to fall back to when the host's parallelism can't be queried.
+const DEFAULT_POOL_SIZE: usize = 4;
+
+/// Returned by [`ThreadPool::build`] when asked to create a pool of size zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolCreationError;
+
+impl fmt::Display for PoolCreationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "thread pool size must be greater than zero")
+    }
+}
+
+impl std::error::Error for PoolCreationError {}
 
 pub struct ThreadPool {
     workers: Vec<Worker>,
     // () is the return type of the closure we pass to each worker thread
     sender: Option<mpsc::Sender<Job>>,
+    completed_jobs: Arc<AtomicU64>,
+    queued_jobs: Arc<AtomicUsize>,
 }
 
 impl ThreadPool {
@@ -14,13 +40,24 @@ impl ThreadPool {
     ///
     /// # Panics
     ///
-    /// The `new` function will panic if the size is zero.
+    /// The `new` function will panic if the size is zero. Use [`ThreadPool::build`] to
+    /// handle that case without panicking.
     pub fn new(size: usize) -> ThreadPool {
-        assert!(size > 0);
+        ThreadPool::build(size).expect("thread pool size must be greater than zero")
+    }
+
+    /// Creates a new `ThreadPool`, returning a [`PoolCreationError`] instead of panicking
+    /// if `size` is zero.
+    pub fn build(size: usize) -> Result<ThreadPool, PoolCreationError> {
+        if size == 0 {
+            return Err(PoolCreationError);
+        }
 
         let (sender, receiver) = mpsc::channel();
 
         let receiver = Arc::new(Mutex::new(receiver));
+        let completed_jobs = Arc::new(AtomicU64::new(0));
+        let queued_jobs = Arc::new(AtomicUsize::new(0));
 
         // Pre-allocates space for the vector... faster than using new() and dynamically
         // sizing the vector with push
@@ -30,24 +67,85 @@ impl ThreadPool {
             workers.push(Worker::new(id, Arc::clone(&receiver)));
         }
 
-        ThreadPool {
+        Ok(ThreadPool {
             workers,
             sender: Some(sender),
-        }
+            completed_jobs,
+            queued_jobs,
+        })
+    }
+
+    /// Creates a `ThreadPool` sized to the host's available parallelism, falling back to
+    /// `DEFAULT_POOL_SIZE` threads when that can't be queried.
+    pub fn with_available_parallelism() -> ThreadPool {
+        let size = thread::available_parallelism()
+            .map(NonZeroUsize::get)
+            .unwrap_or(DEFAULT_POOL_SIZE);
+
+        ThreadPool::build(size).expect("available parallelism is never zero")
     }
 
     /// Executes a given job. Wraps the job behind a Box pointer and passes
     /// the pointer into the channel. On the receiving end, one of the worker
     /// threads in the thread pool will pick up the pointer, unwrap it, and
     /// execute the job closure.
+    ///
+    /// `completed_jobs` and `queued_jobs` are updated here, around `f`, via a drop guard
+    /// so the counters stay balanced even if `f` panics; `Worker` itself knows nothing
+    /// about them, so jobs submitted any other way (e.g. directly over the channel, as
+    /// some tests do) simply aren't counted instead of corrupting the counters.
     pub fn execute<F>(&self, f: F)
     where
         F: FnOnce() + Send + 'static,
     {
-        let job = Box::new(f);
+        struct JobGuard {
+            completed_jobs: Arc<AtomicU64>,
+            queued_jobs: Arc<AtomicUsize>,
+        }
+
+        impl Drop for JobGuard {
+            fn drop(&mut self) {
+                self.queued_jobs.fetch_sub(1, Ordering::SeqCst);
+                self.completed_jobs.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let guard = JobGuard {
+            completed_jobs: Arc::clone(&self.completed_jobs),
+            queued_jobs: Arc::clone(&self.queued_jobs),
+        };
 
+        let job = Box::new(move || {
+            let _guard = guard;
+            f();
+        });
+
+        self.queued_jobs.fetch_add(1, Ordering::SeqCst);
         self.sender.as_ref().unwrap().send(job).unwrap();
     }
+
+    /// Returns the total number of jobs that have finished running, whether they
+    /// completed normally or panicked.
+    pub fn completed_jobs(&self) -> u64 {
+        self.completed_jobs.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of jobs that are queued or currently executing.
+    pub fn queue_depth(&self) -> usize {
+        self.queued_jobs.load(Ordering::SeqCst)
+    }
+
+    /// Returns a clone of the shared `completed_jobs` counter, for callers (e.g. a
+    /// `/metrics` route) that need to read it without holding on to the whole pool.
+    pub fn completed_jobs_handle(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.completed_jobs)
+    }
+
+    /// Returns a clone of the shared `queued_jobs` counter, for callers (e.g. a
+    /// `/metrics` route) that need to read it without holding on to the whole pool.
+    pub fn queue_depth_handle(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.queued_jobs)
+    }
 }
 
 impl Drop for ThreadPool {
@@ -82,6 +180,8 @@ impl Drop for ThreadPool {
     ///
     /// # Example
     /// ```rust
+    /// use multithreaded::ThreadPool;
+    ///
     /// {
     ///     let pool = ThreadPool::new(4);
     ///     // Use the thread pool for tasks...
@@ -98,7 +198,18 @@ impl Drop for ThreadPool {
         for mut worker in &mut self.workers.drain(..) {
             println!("Shutting down worker {}", worker.get_id());
 
-            worker.take_thread().join().unwrap();
+            if let Err(payload) = worker.take_thread().join() {
+                let reason = payload
+                    .downcast_ref::<&str>()
+                    .copied()
+                    .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                    .unwrap_or("unknown panic payload");
+
+                eprintln!(
+                    "Worker {} thread ended unexpectedly: {reason}",
+                    worker.get_id()
+                );
+            }
         }
     }
 }
@@ -113,6 +224,18 @@ mod tests {
         let _pool = ThreadPool::new(0);
     }
 
+    #[test]
+    fn build_errors_on_zero_size() {
+        assert!(matches!(ThreadPool::build(0), Err(PoolCreationError)));
+    }
+
+    #[test]
+    fn with_available_parallelism_builds_a_nonempty_pool() {
+        let pool = ThreadPool::with_available_parallelism();
+
+        assert!(!pool.workers.is_empty());
+    }
+
     #[test]
     fn init_valid_thread_pool() {
         let pool = ThreadPool::new(3);
@@ -139,4 +262,26 @@ mod tests {
         let res = *result.lock().unwrap();
         assert_eq!(res, 8);
     }
+
+    #[test]
+    fn completed_jobs_and_queue_depth_are_tracked() {
+        use std::time::Duration;
+
+        let pool = ThreadPool::new(3);
+
+        assert_eq!(pool.completed_jobs(), 0);
+        assert_eq!(pool.queue_depth(), 0);
+
+        for _ in 0..8 {
+            pool.execute(|| {});
+        }
+
+        // Jobs run concurrently across workers; poll until they've all finished.
+        while pool.completed_jobs() < 8 {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        assert_eq!(pool.completed_jobs(), 8);
+        assert_eq!(pool.queue_depth(), 0);
+    }
 }
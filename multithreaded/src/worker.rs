@@ -1,5 +1,6 @@
 use super::Job;
 use std::{
+    panic::{self, AssertUnwindSafe},
     sync::{Arc, Mutex, mpsc},
     thread,
 };
@@ -13,8 +14,10 @@ impl Worker {
     /// Creates a new `Worker` instance with the specified ID and a shared receiver for jobs.
     ///
     /// This method spawns a new thread that continuously listens for incoming jobs from the
-    /// provided `receiver`. Each job is executed by the worker thread. If the `receiver` is
-    /// disconnected (e.g., the sender is dropped), the worker thread will shut down gracefully.
+    /// provided `receiver`. Each job is executed by the worker thread inside `catch_unwind`, so a
+    /// job that panics logs its payload and the worker keeps receiving jobs instead of dying. If
+    /// the `receiver` is disconnected (e.g., the sender is dropped), the worker thread will shut
+    /// down gracefully.
     ///
     /// # Arguments
     /// - `id`: A unique identifier for the worker. This ID is used for logging and debugging purposes.
@@ -28,13 +31,13 @@ impl Worker {
     /// # Behavior
     /// - The worker thread runs in an infinite loop, waiting for jobs from the `receiver`.
     /// - When a job is received, the worker logs its ID and executes the job.
+    /// - If the job panics, the panic is caught, logged, and the worker keeps running.
     /// - If the `receiver` is disconnected, the worker logs its ID and shuts down gracefully.
     ///
     /// # Example
     /// ```rust
-    /// use std::{
-    ///     sync::{Arc, Mutex, mpsc},
-    /// };
+    /// use multithreaded::Worker;
+    /// use std::sync::{Arc, Mutex, mpsc};
     ///
     /// let (_, rx) = mpsc::channel();
     /// let receiver = Arc::new(Mutex::new(rx));
@@ -57,7 +60,15 @@ impl Worker {
                     Ok(job) => {
                         println!("Worker {id} got a job; executing.");
 
-                        job();
+                        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(job)) {
+                            let reason = payload
+                                .downcast_ref::<&str>()
+                                .copied()
+                                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                                .unwrap_or("unknown panic payload");
+
+                            eprintln!("Worker {id} job panicked: {reason}; continuing.");
+                        }
                     }
                     Err(_) => {
                         println!("Worker {id} disconnected; shutting down.");
@@ -83,9 +94,8 @@ impl Worker {
     ///
     /// # Example
     /// ```rust
-    /// use std::{
-    ///     sync::{Arc, Mutex, mpsc},
-    /// };
+    /// use multithreaded::Worker;
+    /// use std::sync::{Arc, Mutex, mpsc};
     ///
     /// let (_, rx) = mpsc::channel();
     /// let receiver = Arc::new(Mutex::new(rx));
@@ -116,16 +126,14 @@ impl Worker {
     ///
     /// # Example
     /// ```rust
-    /// use std::{
-    ///     sync::{Arc, Mutex, mpsc},
-    /// };
+    /// use multithreaded::Worker;
+    /// use std::sync::{Arc, Mutex, mpsc};
     ///
     /// let (_, rx) = mpsc::channel();
     /// let receiver = Arc::new(Mutex::new(rx));
     /// let mut worker = Worker::new(1, Arc::clone(&receiver));
     ///
     /// let handle = worker.take_thread();
-    /// assert!(worker.thread.is_none());
     ///
     /// // Wait for the worker thread to finish
     /// handle.join().unwrap();
@@ -191,4 +199,34 @@ mod tests {
         let res = result.lock().unwrap();
         assert_eq!(*res, 5);
     }
+
+    #[test]
+    fn worker_survives_a_panicking_job() {
+        let (tx, rx) = mpsc::channel();
+        let receiver = Arc::new(Mutex::new(rx));
+        let mut worker = Worker::new(5, Arc::clone(&receiver));
+
+        let result = Arc::new(Mutex::new(0));
+
+        tx.send(Box::new(|| panic!("boom"))).unwrap();
+
+        {
+            let result = Arc::clone(&result);
+            let job = Box::new(move || {
+                let mut res = result.lock().unwrap();
+                *res += 5;
+            });
+
+            tx.send(job).unwrap();
+        }
+
+        // Disconnect worker
+        drop(tx);
+
+        // The worker should keep running after the panic and still pick up the next job
+        worker.take_thread().join().unwrap();
+
+        let res = result.lock().unwrap();
+        assert_eq!(*res, 5);
+    }
 }
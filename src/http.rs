@@ -0,0 +1,112 @@
+use std::{collections::HashMap, io};
+
+/// An HTTP request method, as found on the request line.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+    Head,
+    Other(String),
+}
+
+impl Method {
+    fn parse(raw: &str) -> Method {
+        match raw {
+            "GET" => Method::Get,
+            "POST" => Method::Post,
+            "PUT" => Method::Put,
+            "DELETE" => Method::Delete,
+            "HEAD" => Method::Head,
+            other => Method::Other(other.to_string()),
+        }
+    }
+}
+
+/// A parsed HTTP request line, along with the headers that followed it.
+#[derive(Debug)]
+pub struct Request {
+    pub method: Method,
+    pub target: String,
+    /// Not yet consulted by any handler here, but available for ones that need it.
+    #[allow(dead_code)]
+    pub headers: HashMap<String, String>,
+}
+
+impl Request {
+    /// Parses a request line and its headers out of an iterator of buffered lines, e.g.
+    /// `BufReader::lines()`, stopping at the blank line that ends the headers.
+    ///
+    /// Returns `Ok(None)` if the connection closed before a request line was sent.
+    pub fn parse(mut lines: impl Iterator<Item = io::Result<String>>) -> io::Result<Option<Request>> {
+        let request_line = match lines.next() {
+            Some(line) => line?,
+            None => return Ok(None),
+        };
+
+        let mut parts = request_line.splitn(3, ' ');
+        let method = Method::parse(parts.next().unwrap_or_default());
+        let target = parts.next().unwrap_or_default().to_string();
+
+        let mut headers = HashMap::new();
+
+        for line in lines {
+            let line = line?;
+
+            if line.is_empty() {
+                break;
+            }
+
+            if let Some((name, value)) = line.split_once(':') {
+                headers.insert(name.trim().to_string(), value.trim().to_string());
+            }
+        }
+
+        Ok(Some(Request {
+            method,
+            target,
+            headers,
+        }))
+    }
+
+    /// The request target with any query string stripped off.
+    pub fn path(&self) -> &str {
+        self.target.split('?').next().unwrap_or(&self.target)
+    }
+}
+
+type Handler = Box<dyn Fn(&Request) -> (&'static str, &'static str, String) + Send + Sync>;
+
+/// Maps `(Method, path)` pairs to handlers, each returning a status line, a
+/// `Content-Type`, and a body.
+pub struct Router {
+    routes: HashMap<(Method, String), Handler>,
+}
+
+impl Router {
+    pub fn new() -> Router {
+        Router {
+            routes: HashMap::new(),
+        }
+    }
+
+    /// Registers `handler` to run for requests matching `method` and `path`.
+    pub fn route(mut self, method: Method, path: &str, handler: Handler) -> Router {
+        self.routes.insert((method, path.to_string()), handler);
+        self
+    }
+
+    /// Looks up the handler matching `request`'s method and path, if any.
+    pub fn dispatch(&self, request: &Request) -> Option<(&'static str, &'static str, String)> {
+        self.routes
+            .get(&(request.method.clone(), request.path().to_string()))
+            .map(|handler| handler(request))
+    }
+}
+
+impl Default for Router {
+    fn default() -> Router {
+        Router::new()
+    }
+}
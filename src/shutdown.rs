@@ -0,0 +1,22 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+/// Installs a Ctrl-C handler that flips a shared flag instead of exiting the process.
+///
+/// The accept loop should poll the returned flag between connections with
+/// [`Ordering::SeqCst`] and break out once it is set, letting `ThreadPool::drop` join
+/// any in-flight workers before the process exits.
+pub fn install_handler() -> Arc<AtomicBool> {
+    let shutting_down = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&shutting_down);
+
+    ctrlc::set_handler(move || {
+        println!("[INFO] Received Ctrl-C; shutting down");
+        flag.store(true, Ordering::SeqCst);
+    })
+    .expect("Failed to install Ctrl-C handler");
+
+    shutting_down
+}
@@ -0,0 +1,132 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Serves files out of a fixed root directory, rejecting any request that would
+/// resolve to a path outside of it.
+pub struct StaticFiles {
+    root: PathBuf,
+}
+
+/// Why a [`StaticFiles::serve`] call didn't return file contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StaticFileError {
+    /// The resolved path escaped the configured root (e.g. via `..` or an absolute path).
+    Forbidden,
+    /// No file exists at the resolved path.
+    NotFound,
+}
+
+impl StaticFiles {
+    /// Creates a `StaticFiles` rooted at `root`, which must already exist.
+    pub fn new(root: impl Into<PathBuf>) -> io::Result<StaticFiles> {
+        Ok(StaticFiles {
+            root: fs::canonicalize(root.into())?,
+        })
+    }
+
+    /// Resolves an HTTP request target (e.g. `/images/cat.png`) to a file under the
+    /// root and returns its bytes alongside a guessed `Content-Type`.
+    pub fn serve(&self, target: &str) -> Result<(Vec<u8>, &'static str), StaticFileError> {
+        let relative = match target.trim_start_matches('/') {
+            "" => "index.html",
+            path => path,
+        };
+
+        if Path::new(relative).is_absolute() {
+            return Err(StaticFileError::Forbidden);
+        }
+
+        let candidate = self.root.join(relative);
+        let canonical = fs::canonicalize(&candidate).map_err(|_| StaticFileError::NotFound)?;
+
+        if !canonical.starts_with(&self.root) {
+            return Err(StaticFileError::Forbidden);
+        }
+
+        let contents = fs::read(&canonical).map_err(|_| StaticFileError::NotFound)?;
+
+        Ok((contents, content_type_for(&canonical)))
+    }
+}
+
+fn content_type_for(path: &Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("js") => "text/javascript; charset=utf-8",
+        Some("json") => "application/json",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("svg") => "image/svg+xml",
+        Some("ico") => "image/x-icon",
+        Some("txt") => "text/plain; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs::File, io::Write, thread};
+
+    fn fixture_root() -> PathBuf {
+        let mut dir = env::temp_dir();
+        dir.push(format!(
+            "shirry_webserver_static_files_test_{:?}",
+            thread::current().id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut index = File::create(dir.join("index.html")).unwrap();
+        index.write_all(b"<html>hi</html>").unwrap();
+
+        let mut secret = File::create(env::temp_dir().join("secret.txt")).unwrap();
+        secret.write_all(b"top secret").unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn serves_a_file_under_the_root() {
+        let root = fixture_root();
+        let static_files = StaticFiles::new(&root).unwrap();
+
+        let (contents, content_type) = static_files.serve("/index.html").unwrap();
+
+        assert_eq!(contents, b"<html>hi</html>");
+        assert_eq!(content_type, "text/html; charset=utf-8");
+    }
+
+    #[test]
+    fn empty_target_serves_index_html() {
+        let root = fixture_root();
+        let static_files = StaticFiles::new(&root).unwrap();
+
+        let (contents, _) = static_files.serve("/").unwrap();
+
+        assert_eq!(contents, b"<html>hi</html>");
+    }
+
+    #[test]
+    fn rejects_path_traversal_out_of_the_root() {
+        let root = fixture_root();
+        let static_files = StaticFiles::new(&root).unwrap();
+
+        let err = static_files.serve("/../secret.txt").unwrap_err();
+
+        assert_eq!(err, StaticFileError::Forbidden);
+    }
+
+    #[test]
+    fn missing_file_is_not_found() {
+        let root = fixture_root();
+        let static_files = StaticFiles::new(&root).unwrap();
+
+        let err = static_files.serve("/does-not-exist.html").unwrap_err();
+
+        assert_eq!(err, StaticFileError::NotFound);
+    }
+}